@@ -1,12 +1,114 @@
 #![no_std]
 
+/// Precomputed CRC16/Modbus remainders, indexed by `(register ^ next_byte) & 0xFF`, so
+/// `crc16`/`crc16_update` cost one table lookup per byte instead of 8 shift-and-xor steps.
+/// Gated behind the `crc-table` feature so extremely small targets can keep the bitwise version.
+#[cfg(feature = "crc-table")]
+#[rustfmt::skip]
+const CRC16_TABLE: [u16; 256] = [
+    0x0000, 0xC0C1, 0xC181, 0x0140, 0xC301, 0x03C0, 0x0280, 0xC241,
+    0xC601, 0x06C0, 0x0780, 0xC741, 0x0500, 0xC5C1, 0xC481, 0x0440,
+    0xCC01, 0x0CC0, 0x0D80, 0xCD41, 0x0F00, 0xCFC1, 0xCE81, 0x0E40,
+    0x0A00, 0xCAC1, 0xCB81, 0x0B40, 0xC901, 0x09C0, 0x0880, 0xC841,
+    0xD801, 0x18C0, 0x1980, 0xD941, 0x1B00, 0xDBC1, 0xDA81, 0x1A40,
+    0x1E00, 0xDEC1, 0xDF81, 0x1F40, 0xDD01, 0x1DC0, 0x1C80, 0xDC41,
+    0x1400, 0xD4C1, 0xD581, 0x1540, 0xD701, 0x17C0, 0x1680, 0xD641,
+    0xD201, 0x12C0, 0x1380, 0xD341, 0x1100, 0xD1C1, 0xD081, 0x1040,
+    0xF001, 0x30C0, 0x3180, 0xF141, 0x3300, 0xF3C1, 0xF281, 0x3240,
+    0x3600, 0xF6C1, 0xF781, 0x3740, 0xF501, 0x35C0, 0x3480, 0xF441,
+    0x3C00, 0xFCC1, 0xFD81, 0x3D40, 0xFF01, 0x3FC0, 0x3E80, 0xFE41,
+    0xFA01, 0x3AC0, 0x3B80, 0xFB41, 0x3900, 0xF9C1, 0xF881, 0x3840,
+    0x2800, 0xE8C1, 0xE981, 0x2940, 0xEB01, 0x2BC0, 0x2A80, 0xEA41,
+    0xEE01, 0x2EC0, 0x2F80, 0xEF41, 0x2D00, 0xEDC1, 0xEC81, 0x2C40,
+    0xE401, 0x24C0, 0x2580, 0xE541, 0x2700, 0xE7C1, 0xE681, 0x2640,
+    0x2200, 0xE2C1, 0xE381, 0x2340, 0xE101, 0x21C0, 0x2080, 0xE041,
+    0xA001, 0x60C0, 0x6180, 0xA141, 0x6300, 0xA3C1, 0xA281, 0x6240,
+    0x6600, 0xA6C1, 0xA781, 0x6740, 0xA501, 0x65C0, 0x6480, 0xA441,
+    0x6C00, 0xACC1, 0xAD81, 0x6D40, 0xAF01, 0x6FC0, 0x6E80, 0xAE41,
+    0xAA01, 0x6AC0, 0x6B80, 0xAB41, 0x6900, 0xA9C1, 0xA881, 0x6840,
+    0x7800, 0xB8C1, 0xB981, 0x7940, 0xBB01, 0x7BC0, 0x7A80, 0xBA41,
+    0xBE01, 0x7EC0, 0x7F80, 0xBF41, 0x7D00, 0xBDC1, 0xBC81, 0x7C40,
+    0xB401, 0x74C0, 0x7580, 0xB541, 0x7700, 0xB7C1, 0xB681, 0x7640,
+    0x7200, 0xB2C1, 0xB381, 0x7340, 0xB101, 0x71C0, 0x7080, 0xB041,
+    0x5000, 0x90C1, 0x9181, 0x5140, 0x9301, 0x53C0, 0x5280, 0x9241,
+    0x9601, 0x56C0, 0x5780, 0x9741, 0x5500, 0x95C1, 0x9481, 0x5440,
+    0x9C01, 0x5CC0, 0x5D80, 0x9D41, 0x5F00, 0x9FC1, 0x9E81, 0x5E40,
+    0x5A00, 0x9AC1, 0x9B81, 0x5B40, 0x9901, 0x59C0, 0x5880, 0x9841,
+    0x8801, 0x48C0, 0x4980, 0x8941, 0x4B00, 0x8BC1, 0x8A81, 0x4A40,
+    0x4E00, 0x8EC1, 0x8F81, 0x4F40, 0x8D01, 0x4DC0, 0x4C80, 0x8C41,
+    0x4400, 0x84C1, 0x8581, 0x4540, 0x8701, 0x47C0, 0x4680, 0x8641,
+    0x8201, 0x42C0, 0x4380, 0x8341, 0x4100, 0x81C1, 0x8081, 0x4040,
+];
+
+/// Which side of a Modbus exchange a buffer is decoding, used to pick the length table
+/// that predicts a frame's total size from its header.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum FrameRole {
+    /// Bytes are a master request.
+    Request,
+    /// Bytes are a slave response.
+    Response,
+}
+
+/// Link-layer framing mode used to locate a Modbus frame in the buffer.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Protocol {
+    /// Serial framing: PDU followed by a trailing CRC16 (the default).
+    Rtu,
+    /// Modbus TCP framing: a 7-byte MBAP header (no checksum) in front of the PDU.
+    TcpMbap,
+}
+
+/// Outcome of `try_decode`, distinguishing "wait for more bytes" from "the buffered bytes are
+/// definitively unparseable noise" so callers can tell those two apart instead of getting `None`
+/// for both.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DecodeStatus {
+    /// A full frame was decoded; `len` is its length, and `transaction_id` carries the MBAP
+    /// transaction ID when decoding `Protocol::TcpMbap` (`None` for `Protocol::Rtu`).
+    Complete { len: usize, transaction_id: Option<u16> },
+    /// A valid prefix may be buffered, but more bytes are needed before a frame can be decoded.
+    Partial,
+    /// The buffer was full of bytes that could not form a valid frame; the leading byte was
+    /// discarded so a saturated buffer can recover instead of getting stuck forever.
+    Invalid { discarded: usize },
+}
+
+/// Result of attempting the length-aware decode path, kept internal since `try_decode`
+/// needs to tell "incomplete, don't fall back to scanning" apart from "no length to predict,
+/// fall back to scanning".
+enum LengthAwareOutcome {
+    /// Not enough bytes buffered yet to know the predicted length, or to hold all of it.
+    Incomplete,
+    /// Either the header doesn't predict a length, or the CRC over the predicted length failed.
+    NotApplicable,
+    /// The predicted length was fully buffered and its CRC matched.
+    Matched(usize, usize),
+}
+
+/// Outcome of predicting a frame's total length from its header: either a length-predictable
+/// function code whose length-determining field (byte count, where applicable) has fully
+/// arrived, one whose header is still too short to read that field, or one that isn't
+/// length-predictable at all. Kept distinct from `LengthAwareOutcome` since "header incomplete"
+/// must fall through to waiting for more bytes, not to CRC scanning the partial header, while
+/// "not predictable" is what actually falls back to scanning.
+enum FrameLenOutcome {
+    /// The total frame length (PDU + 2 CRC bytes).
+    Known(usize),
+    /// The function code is length-predictable, but not enough header bytes are buffered yet
+    /// to read the field that determines the length.
+    HeaderIncomplete,
+    /// The function code isn't length-predictable for this `FrameRole`.
+    NotPredictable,
+}
+
 #[derive(Debug)]
 pub struct ModbusBuffer<const CAPACITY: usize> {
-    ring_buffer: [Option<u8>;CAPACITY],
+    ring_buffer: [u8;CAPACITY],
     /// Head of data (Oldest Byte)
     head: usize,
-    /// Tail of data (Newest Byte)
-    tail: usize,
+    /// Number of occupied bytes starting at `head`. The tail (newest byte, exclusive) is
+    /// `(head + size) % CAPACITY` and is never stored separately.
     size: usize,
     /// Min frame length to be detected (CRC not included) (Default: 3)
     min_frame_len: usize,
@@ -14,6 +116,10 @@ pub struct ModbusBuffer<const CAPACITY: usize> {
     max_frame_len: usize,
     /// Whether the buffer should be overwritten if overflowed, or panic (Default: `true`).
     overwrite: bool,
+    /// If set, enables length-aware framing for this role before falling back to CRC scanning (Default: `None`).
+    frame_role: Option<FrameRole>,
+    /// Link-layer framing mode to decode (Default: `Protocol::Rtu`).
+    protocol: Protocol,
 }
 impl<const CAPACITY: usize> ModbusBuffer<CAPACITY> {
 
@@ -23,13 +129,14 @@ impl<const CAPACITY: usize> ModbusBuffer<CAPACITY> {
         
         assert!(CAPACITY > 4);
         ModbusBuffer {
-            ring_buffer: [None; CAPACITY],
+            ring_buffer: [0u8; CAPACITY],
             head: 0,
-            tail: 0,
             size: 0,
             min_frame_len: 3,
             max_frame_len: CAPACITY,
             overwrite: true,
+            frame_role: None,
+            protocol: Protocol::Rtu,
         }
     }
 
@@ -51,23 +158,34 @@ impl<const CAPACITY: usize> ModbusBuffer<CAPACITY> {
             self
         }
 
-    /// Adds an item to the buffer, handling overflow based on the `overwrite` flag.
+    /// Enables length-aware deterministic framing for the given `FrameRole`, computing the
+    /// exact frame boundary from the Modbus header before falling back to CRC scanning.
+    pub fn frame_role(mut self, frame_role: FrameRole) -> Self {
+        self.frame_role = Some(frame_role);
+        self
+    }
+
+    /// Selects the link-layer framing mode to decode (Default: `Protocol::Rtu`).
+    pub fn protocol(mut self, protocol: Protocol) -> Self {
+        self.protocol = protocol;
+        self
+    }
 
+    /// Adds an item to the buffer, handling overflow based on the `overwrite` flag.
     pub fn push(&mut self, item: u8) {
         if self.size == CAPACITY {
             // Buffer is full
             if self.overwrite {
-                self.ring_buffer[self.head] = Some(item);
+                self.ring_buffer[self.head] = item;
                 self.head = (self.head + 1) % CAPACITY;
-                self.tail = (self.tail + 1) % CAPACITY;
             } else {
                 panic!("ModbusBuffer exceed its capacity!");
             }
 
         } else {
             // Buffer has space
-            self.ring_buffer[self.tail] = Some(item);
-            self.tail = (self.tail + 1) % CAPACITY;
+            let tail = (self.head + self.size) % CAPACITY;
+            self.ring_buffer[tail] = item;
             self.size += 1;
         }
     }
@@ -78,10 +196,9 @@ impl<const CAPACITY: usize> ModbusBuffer<CAPACITY> {
             None
         } else {
             let item = self.ring_buffer[self.head];
-            self.ring_buffer[self.head] = None;
             self.head = (self.head + 1) % CAPACITY;
             self.size -= 1;
-            item
+            Some(item)
         }
     }
 
@@ -100,42 +217,74 @@ impl<const CAPACITY: usize> ModbusBuffer<CAPACITY> {
         self.size == CAPACITY
     }
 
+    /// Returns the occupied region, oldest byte first, as one or two contiguous sub-slices
+    /// (the second is empty unless the data wraps around the end of the backing array).
+    pub fn get_allocated(&self) -> (&[u8], &[u8]) {
+        if self.size == 0 {
+            return (&[], &[]);
+        }
+        let first_len = (CAPACITY - self.head).min(self.size);
+        let second_len = self.size - first_len;
+        (&self.ring_buffer[self.head..self.head + first_len], &self.ring_buffer[..second_len])
+    }
+
+    /// Returns the free region, starting right after the newest byte, as one or two contiguous
+    /// sub-slices (the second is empty unless the free space wraps around the end of the
+    /// backing array). Bytes written into these slices are not counted as buffered: `len()`
+    /// only grows through `push` and `enqueue_slice`, and no method commits bytes written here.
+    pub fn get_unallocated(&mut self) -> (&mut [u8], &mut [u8]) {
+        let free = CAPACITY - self.size;
+        if free == 0 {
+            return (&mut [], &mut []);
+        }
+        let tail = (self.head + self.size) % CAPACITY;
+        let first_len = (CAPACITY - tail).min(free);
+        let second_len = free - first_len;
+        let (before_tail, from_tail) = self.ring_buffer.split_at_mut(tail);
+        (&mut from_tail[..first_len], &mut before_tail[..second_len])
+    }
+
+    /// Copies as much of `data` as fits into the free space and advances the tail accordingly.
+    /// If `overwrite` is `false` and `data` doesn't fit in the remaining free space, panics the
+    /// same way `push` does on overflow instead of silently truncating. Otherwise returns the
+    /// number of bytes actually written, which is less than `data.len()` if the buffer filled
+    /// up partway through.
+    pub fn enqueue_slice(&mut self, data: &[u8]) -> usize {
+        if !self.overwrite && data.len() > CAPACITY - self.size {
+            panic!("ModbusBuffer exceed its capacity!");
+        }
+
+        let (first, second) = self.get_unallocated();
+        let first_n = first.len().min(data.len());
+        first[..first_n].copy_from_slice(&data[..first_n]);
+
+        let second_n = second.len().min(data.len() - first_n);
+        second[..second_n].copy_from_slice(&data[first_n..first_n + second_n]);
+
+        self.size += first_n + second_n;
+        first_n + second_n
+    }
+
+    /// Removes up to `n` oldest bytes from the buffer, advancing the head. Returns the number
+    /// of bytes actually removed, which is less than `n` if fewer than `n` were buffered.
+    pub fn dequeue_many(&mut self, n: usize) -> usize {
+        let removed = n.min(self.size);
+        self.head = (self.head + removed) % CAPACITY;
+        self.size -= removed;
+        removed
+    }
+
     /// Copies the current data from the buffer into the provided output buffer and returns the size.
     fn frame(&self, output_buffer: &mut [u8;CAPACITY]) -> Option<usize> {
-        let mut index = 0;
-
-        if self.size > 0 {
-            if self.head < self.tail {
-                // No wrap-around, direct slice
-                self.ring_buffer[self.head..self.tail]
-                    .iter()
-                    .for_each(|d| {
-                        output_buffer[index] = d.unwrap();
-                        index += 1;
-                    });
-            } else {
-                // Wrap-around, handle two parts
-                // First part from head to end of buffer
-                self.ring_buffer[self.head..CAPACITY]
-                    .iter()
-                    .for_each(|d| {
-                        output_buffer[index] = d.unwrap();
-                        index += 1;
-                    });
-
-                // Second part from start of buffer to tail
-                self.ring_buffer[0..self.tail]
-                    .iter()
-                    .for_each(|d| {
-                        output_buffer[index] = d.unwrap();
-                        index += 1;
-                    });
-            }
-        }
+        let (first, second) = self.get_allocated();
+        output_buffer[..first.len()].copy_from_slice(first);
+        output_buffer[first.len()..first.len() + second.len()].copy_from_slice(second);
         Some(self.size)
     }
 
-    /// Computes the CRC16 for the provided data array.
+    /// Computes the CRC16 for the provided data array, one bit at a time.
+    /// Kept for targets too small to afford the 256-entry `crc-table` lookup table.
+    #[cfg(not(feature = "crc-table"))]
     fn crc16(data: &[u8]) -> u16 {
         let mut crc = 0xFFFF;
         for x in data {
@@ -152,7 +301,26 @@ impl<const CAPACITY: usize> ModbusBuffer<CAPACITY> {
                 }
             }
         }
-        crc << 8 | crc >> 8
+        crc.rotate_left(8)
+    }
+
+    /// Folds one more byte into a running CRC16 register via the lookup table, without the
+    /// final byte swap `crc16`/`check_crc` apply. Lets the frame scanner maintain a single
+    /// register per candidate start index instead of recomputing the whole window every time.
+    #[cfg(feature = "crc-table")]
+    fn crc16_update(crc: u16, byte: u8) -> u16 {
+        let index = ((crc ^ u16::from(byte)) & 0x00FF) as usize;
+        (crc >> 8) ^ CRC16_TABLE[index]
+    }
+
+    /// Computes the CRC16 for the provided data array, one table lookup per byte.
+    #[cfg(feature = "crc-table")]
+    fn crc16(data: &[u8]) -> u16 {
+        let mut crc = 0xFFFF;
+        for &byte in data {
+            crc = Self::crc16_update(crc, byte);
+        }
+        crc.rotate_left(8)
     }
 
     /// Verifies the CRC of the provided frame.
@@ -167,7 +335,64 @@ impl<const CAPACITY: usize> ModbusBuffer<CAPACITY> {
 
     }
 
-    /// Tries to find a valid Modbus frame in the buffer.
+    /// Computes the total frame length (PDU + 2 CRC bytes) from the Modbus header,
+    /// following the length semantics for the given `FrameRole`. See `FrameLenOutcome`.
+    fn expected_frame_len(frame: &[u8], role: FrameRole) -> FrameLenOutcome {
+        let Some(&function_code) = frame.get(1) else {
+            return FrameLenOutcome::HeaderIncomplete;
+        };
+
+        // Exception response: address + function(|0x80) + exception code + CRC
+        if function_code & 0x80 != 0 {
+            return FrameLenOutcome::Known(5);
+        }
+
+        match role {
+            FrameRole::Request => match function_code {
+                0x01..=0x06 => FrameLenOutcome::Known(8),
+                0x0F | 0x10 => match frame.get(6) {
+                    // header through byte_count is 7 bytes (indices 0-6), plus the data and
+                    // the trailing 2-byte CRC.
+                    Some(&byte_count) => FrameLenOutcome::Known(7 + byte_count as usize + 2),
+                    None => FrameLenOutcome::HeaderIncomplete,
+                },
+                _ => FrameLenOutcome::NotPredictable,
+            },
+            FrameRole::Response => match function_code {
+                0x01..=0x04 => match frame.get(2) {
+                    Some(&byte_count) => FrameLenOutcome::Known(3 + byte_count as usize + 2),
+                    None => FrameLenOutcome::HeaderIncomplete,
+                },
+                0x05 | 0x06 | 0x0F | 0x10 => FrameLenOutcome::Known(8),
+                _ => FrameLenOutcome::NotPredictable,
+            },
+        }
+    }
+
+    /// Outcome of the length-aware decode path, distinguishing "wait for more bytes" from
+    /// "this header doesn't predict a length" and "header predicts a length, and the CRC
+    /// over it matches".
+    fn try_decode_length_aware(buffer: &[u8], role: FrameRole) -> LengthAwareOutcome {
+        let total_len = match Self::expected_frame_len(buffer, role) {
+            FrameLenOutcome::Known(len) => len,
+            // Not enough header bytes yet to even know the length: wait for more, don't scan.
+            FrameLenOutcome::HeaderIncomplete => return LengthAwareOutcome::Incomplete,
+            FrameLenOutcome::NotPredictable => return LengthAwareOutcome::NotApplicable,
+        };
+        if buffer.len() < total_len {
+            // Not enough bytes yet to validate the frame: wait for more, don't scan.
+            return LengthAwareOutcome::Incomplete;
+        }
+        if Self::check_crc(&buffer[..total_len]) {
+            LengthAwareOutcome::Matched(0, total_len - 2)
+        } else {
+            LengthAwareOutcome::NotApplicable
+        }
+    }
+
+    /// Tries to find a valid Modbus frame in the buffer, recomputing the CRC16 from scratch
+    /// for every candidate window. O(n^2 * k); kept for the `crc-table` opt-out.
+    #[cfg(not(feature = "crc-table"))]
     fn try_decode_buffer(&self, buffer: &[u8]) -> Option<(usize, usize)> {
         let mut window_size = self.min_frame_len + 2;
         if buffer.len() < window_size {
@@ -195,44 +420,168 @@ impl<const CAPACITY: usize> ModbusBuffer<CAPACITY> {
         None
     }
 
-    /// Attempts to decode a Modbus frame from the internal buffer and copies it into the provided buffer if successful.
-    pub fn try_decode_frame(&mut self, buffer: &mut [u8;CAPACITY]) -> Option<usize> {
-        if self.size == 0 || self.size < self.min_frame_len {
+    /// Tries to find a valid Modbus frame in the buffer, visiting candidates in the same
+    /// forward/reverse-interleaved order as the non-table scan (ascending `window_size`, and
+    /// for each window a forward probe at `i` followed by a reverse-mirrored probe at `j`)
+    /// so the two implementations never disagree on which frame a noisy buffer decodes to.
+    /// One running CRC register per start index is extended by a single table lookup as
+    /// `window_size` grows, instead of recomputing each window's CRC from scratch, bringing
+    /// the scan down from O(n^2 * k) to roughly O(n^2).
+    #[cfg(feature = "crc-table")]
+    fn try_decode_buffer(&self, buffer: &[u8]) -> Option<(usize, usize)> {
+        let mut window_size = self.min_frame_len + 2;
+        if buffer.len() < window_size {
             return None
         }
 
+        // `registers[i]` holds the running CRC over `buffer[i..i + (window_size - 2)]`, i.e.
+        // the PDU of the candidate frame currently starting at `i`.
+        let mut registers = [0xFFFFu16; CAPACITY];
+        for (i, crc) in registers.iter_mut().enumerate().take(buffer.len() - window_size + 1) {
+            for &byte in &buffer[i..i + self.min_frame_len] {
+                *crc = Self::crc16_update(*crc, byte);
+            }
+        }
+
+        loop {
+            for i in 0..=buffer.len() - window_size {
+                // `check_crc` rejects frames of 4 bytes or fewer; mirror that here.
+                if window_size > 4
+                    && Self::matches_register(registers[i], &buffer[i + window_size - 2..i + window_size]) {
+                    return Some((i, i + window_size - 2));
+                }
+
+                if buffer.len() == window_size {
+                    return None;
+                }
+
+                let j = buffer.len() - i - window_size;
+                if window_size > 4
+                    && Self::matches_register(registers[j], &buffer[j + window_size - 2..j + window_size]) {
+                    return Some((j, j + window_size - 2));
+                }
+            }
+
+            window_size += 1;
+            if window_size > buffer.len() {
+                return None;
+            }
+            for (i, crc) in registers.iter_mut().enumerate().take(buffer.len() - window_size + 1) {
+                *crc = Self::crc16_update(*crc, buffer[i + window_size - 3]);
+            }
+        }
+    }
+
+    /// Checks a running CRC register (folded over a candidate frame's PDU) against that
+    /// frame's trailing 2 CRC bytes, the table-driven counterpart of `check_crc`.
+    #[cfg(feature = "crc-table")]
+    fn matches_register(crc: u16, trailing: &[u8]) -> bool {
+        let swapped = crc.rotate_left(8);
+        let expected = [(swapped >> 8) as u8, swapped as u8];
+        expected == trailing
+    }
+
+    /// Tries to find an MBAP-framed Modbus TCP frame at the start of the buffer.
+    /// Returns `(head, tail, transaction_id)` on success, where `frame[head..tail]` is the
+    /// unit id followed by the PDU (the first 6 header bytes, transaction id, protocol id and
+    /// length, are not included). No checksum is
+    /// validated; a non-zero protocol ID is rejected as not a Modbus TCP frame.
+    fn try_decode_mbap(buffer: &[u8]) -> Option<(usize, usize, u16)> {
+        const MBAP_HEADER_LEN: usize = 7;
+        if buffer.len() < MBAP_HEADER_LEN {
+            return None;
+        }
+        let transaction_id = u16::from_be_bytes([buffer[0], buffer[1]]);
+        let protocol_id = u16::from_be_bytes([buffer[2], buffer[3]]);
+        if protocol_id != 0x0000 {
+            return None;
+        }
+        let length_field = u16::from_be_bytes([buffer[4], buffer[5]]) as usize;
+        let total_len = 6 + length_field;
+        if buffer.len() < total_len {
+            return None;
+        }
+        Some((6, total_len, transaction_id))
+    }
+
+    /// Attempts to decode a Modbus frame from the internal buffer and copies it into the
+    /// provided buffer, reporting whether it completed, is waiting on more bytes, or had to
+    /// discard un-decodable leading noise. See `DecodeStatus`.
+    pub fn try_decode(&mut self, buffer: &mut [u8;CAPACITY]) -> DecodeStatus {
+        if self.size == 0 || self.size < self.min_frame_len {
+            return DecodeStatus::Partial
+        }
+
         let mut frame = [0u8;CAPACITY];
         // copy data into `frame`
         let len = self.frame(&mut frame).expect("Should have a frame");
 
-        if let Some((head, tail)) = self.try_decode_buffer(&frame[..len]) {
-            // if CRC match
-            
-            ///  println! is std, not available w/ `#![no_std]` flag
-            // println!(" ");
-            // println!("---------------- CRC Match!! ------------------------");
-            // println!("len={}, tail={}, head={},", len, tail, head);
-            // println!("frame={:?}", frame);
-            // println!("self={:?}", self);
-            // println!("---------------- CRC Match end ------------------------");
-
-            // remove decoded data
-            // let len_to_remove = len - tail + 2 ;
-            let len_to_remove = tail + 2 ;
-            for _ in 0..len_to_remove {
-                self.pop();
+        match self.protocol {
+            Protocol::TcpMbap => match Self::try_decode_mbap(&frame[..len]) {
+                Some((head, tail, transaction_id)) => {
+                    // remove the whole decoded MBAP frame, including its header
+                    for _ in 0..tail {
+                        self.pop();
+                    }
+
+                    let frame_length = tail - head;
+                    buffer[..frame_length].copy_from_slice(&frame[head..tail]);
+                    DecodeStatus::Complete { len: frame_length, transaction_id: Some(transaction_id) }
+                }
+                None => self.give_up_if_full(),
+            },
+            Protocol::Rtu => {
+                let decoded = match self.frame_role {
+                    Some(role) => match Self::try_decode_length_aware(&frame[..len], role) {
+                        LengthAwareOutcome::Matched(head, tail) => Some((head, tail)),
+                        LengthAwareOutcome::Incomplete => None,
+                        LengthAwareOutcome::NotApplicable => self.try_decode_buffer(&frame[..len]),
+                    },
+                    None => self.try_decode_buffer(&frame[..len]),
+                };
+
+                match decoded {
+                    Some((head, tail)) => {
+                        // remove decoded data
+                        let len_to_remove = tail + 2 ;
+                        for _ in 0..len_to_remove {
+                            self.pop();
+                        }
+
+                        // output frame length
+                        let frame_length = tail - head;
+
+                        // copy data
+                        buffer[..frame_length].copy_from_slice(&frame[head..tail]);
+
+                        DecodeStatus::Complete { len: frame_length, transaction_id: None }
+                    }
+                    None => self.give_up_if_full(),
+                }
             }
+        }
+    }
 
-            // output frame length
-            let frame_length = tail - head;
-
-            // copy data
-            buffer[..frame_length].copy_from_slice(&frame[head..tail]);
-
-            // return frame length
-            Some(frame_length)
+    /// Called once no frame could be decoded from the current contents: if the buffer is full,
+    /// it's saturated with noise no valid frame will ever fit around, so drop the oldest byte
+    /// and report it as discarded instead of leaving the buffer stuck. Otherwise more bytes may
+    /// still complete a frame, so just ask the caller to wait.
+    fn give_up_if_full(&mut self) -> DecodeStatus {
+        if self.is_full() {
+            self.pop();
+            DecodeStatus::Invalid { discarded: 1 }
         } else {
-            None
+            DecodeStatus::Partial
+        }
+    }
+
+    /// Thin wrapper over `try_decode` for callers that only care about a successful decode,
+    /// returning its length alongside the MBAP transaction ID when decoding `Protocol::TcpMbap`
+    /// (always `None` for `Protocol::Rtu`).
+    pub fn try_decode_frame(&mut self, buffer: &mut [u8;CAPACITY]) -> Option<(usize, Option<u16>)> {
+        match self.try_decode(buffer) {
+            DecodeStatus::Complete { len, transaction_id } => Some((len, transaction_id)),
+            DecodeStatus::Partial | DecodeStatus::Invalid { .. } => None,
         }
     }
 }
@@ -309,7 +658,7 @@ mod tests {
 
         let mut output = [0u8;10];
         let len = buff.try_decode_frame(&mut output);
-        assert_eq!(len, Some(6));
+        assert_eq!(len, Some((6, None)));
         assert_eq!(output, [0x12u8, 0x06, 0x22, 0x22, 0xAB, 0xCD, 0, 0, 0, 0]);
 
     }
@@ -345,7 +694,7 @@ mod tests {
 
         // now request is complete
         let len = buff.try_decode_frame(&mut output);
-        assert_eq!(len, Some(6));
+        assert_eq!(len, Some((6, None)));
 
         assert_eq!(output, [0x12u8, 0x06, 0x22, 0x22, 0xAB, 0xCD, 0, 0, 0, 0]);
 
@@ -365,7 +714,7 @@ mod tests {
 
         let mut output = [0u8;10];
         let len = buff.try_decode_frame(&mut output);
-        assert_eq!(len, Some(6));
+        assert_eq!(len, Some((6, None)));
         assert_eq!(output, [0x12u8, 0x06, 0x22, 0x22, 0xAB, 0xCD, 0, 0, 0, 0]);
 
         // new request
@@ -380,7 +729,7 @@ mod tests {
 
         let mut output = [0u8;10];
         let len = buff.try_decode_frame(&mut output);
-        assert_eq!(len, Some(6));
+        assert_eq!(len, Some((6, None)));
         assert_eq!(output, [0x12u8, 0x06, 0x22, 0x22, 0xAB, 0xCD, 0, 0, 0, 0]);
 
         // new request
@@ -395,7 +744,7 @@ mod tests {
 
         let mut output = [0u8;10];
         let len = buff.try_decode_frame(&mut output);
-        assert_eq!(len, Some(6));
+        assert_eq!(len, Some((6, None)));
         assert_eq!(output, [0x12u8, 0x06, 0x22, 0x22, 0xAB, 0xCD, 0, 0, 0, 0]);
 
         // new request
@@ -410,7 +759,7 @@ mod tests {
 
         let mut output = [0u8;10];
         let len = buff.try_decode_frame(&mut output);
-        assert_eq!(len, Some(6));
+        assert_eq!(len, Some((6, None)));
         assert_eq!(output, [0x12u8, 0x06, 0x22, 0x22, 0xAB, 0xCD, 0, 0, 0, 0]);
 
     }
@@ -446,7 +795,7 @@ mod tests {
 
         let mut output = [0u8;10];
         let len = buff.try_decode_frame(&mut output);
-        assert_eq!(len, Some(6));
+        assert_eq!(len, Some((6, None)));
         assert_eq!(output, [0x12u8, 0x06, 0x22, 0x22, 0xAB, 0xCD, 0, 0, 0, 0]);
         
         assert_eq!(buff.len(), 0usize);
@@ -481,12 +830,12 @@ mod tests {
         
         // decode the first request but do not remove the second request on tail
         let len = buff.try_decode_frame(&mut output);
-        assert_eq!(len, Some(6));
+        assert_eq!(len, Some((6, None)));
         assert_eq!(buff.len(), 8usize);
 
         // decode second request
         let len = buff.try_decode_frame(&mut output);
-        assert_eq!(len, Some(6));
+        assert_eq!(len, Some((6, None)));
         assert_eq!(buff.len(), 0usize);
 
     }
@@ -521,7 +870,7 @@ mod tests {
 
         // decode the second request, first one have been partially overwritten
         let len = buff.try_decode_frame(&mut output);
-        assert_eq!(len, Some(6));
+        assert_eq!(len, Some((6, None)));
         assert_eq!(buff.len(), 0usize);
     }
     
@@ -536,5 +885,428 @@ mod tests {
         let q = buff.try_decode_frame(&mut temp);
         assert_eq!(q, None);
     }
-    
+
+    #[test]
+    fn test_length_aware_request() {
+        let mut buff = ModbusBuffer::<20>::new().frame_role(FrameRole::Request);
+
+        // read holding registers request, fixed 8 bytes
+        buff.push(0x11);    // slave addr
+        buff.push(0x03);    // function code
+        buff.push(0x00);    // addr
+        buff.push(0x6B);    // addr
+
+        // not yet complete
+        let mut output = [0u8;20];
+        let len = buff.try_decode_frame(&mut output);
+        assert_eq!(len, None);
+
+        buff.push(0x00);    // qty
+        buff.push(0x03);    // qty
+        buff.push(0x76);    // crc
+        buff.push(0x87);    // crc
+
+        let len = buff.try_decode_frame(&mut output);
+        assert_eq!(len, Some((6, None)));
+        assert_eq!(&output[..6], &[0x11u8, 0x03, 0x00, 0x6B, 0x00, 0x03]);
+    }
+
+    #[test]
+    fn test_length_aware_multi_write_request() {
+        let mut buff = ModbusBuffer::<20>::new().frame_role(FrameRole::Request);
+
+        // write multiple registers request: byte_count at index 6 drives the length, and the
+        // frame must decode as soon as its own CRC arrives, without waiting on a follow-on frame
+        let frame = [
+            0x11u8, 0x10, 0x00, 0x01, 0x00, 0x02, 0x04, 0x00, 0x0A, 0x01, 0x02, 0xC6, 0xF0,
+        ];
+        for b in frame {
+            buff.push(b);
+        }
+
+        let mut output = [0u8;20];
+        let len = buff.try_decode_frame(&mut output);
+        assert_eq!(len, Some((11, None)));
+        assert_eq!(&output[..11], &frame[..11]);
+    }
+
+    #[test]
+    fn test_length_aware_multi_write_request_waits_for_byte_count() {
+        let mut buff = ModbusBuffer::<20>::new().frame_role(FrameRole::Request);
+
+        // a multi-write request whose header hasn't reached the byte_count field at index 6
+        // yet (only 5 of its 7 header bytes buffered). The trailing 2 bytes happen to pass
+        // check_crc as a bogus 3-byte frame over the still-partial header; length-aware framing
+        // must recognize the header itself is incomplete and wait, not fall back to scanning it.
+        let frame = [0x00u8, 0x10, 0x00, 0x7C, 0x00];
+        for b in frame {
+            buff.push(b);
+        }
+
+        let mut output = [0u8;20];
+        let len = buff.try_decode_frame(&mut output);
+        assert_eq!(len, None);
+    }
+
+    #[test]
+    fn test_length_aware_response_with_byte_count() {
+        let mut buff = ModbusBuffer::<20>::new().frame_role(FrameRole::Response);
+
+        // read holding registers response, byte count at index 2 drives the length
+        let frame = [0x11u8, 0x03, 0x06, 0x02, 0x2B, 0x00, 0x00, 0x00, 0x64, 0xC8, 0xBA];
+        for b in frame {
+            buff.push(b);
+        }
+
+        let mut output = [0u8;20];
+        let len = buff.try_decode_frame(&mut output);
+        assert_eq!(len, Some((9, None)));
+        assert_eq!(&output[..9], &frame[..9]);
+    }
+
+    #[test]
+    fn test_tcp_mbap_frame() {
+        let mut buff = ModbusBuffer::<20>::new().protocol(Protocol::TcpMbap);
+
+        // transaction id 0x0007, protocol id 0x0000, length 6, unit id 0x11, PDU read holding regs
+        let frame = [
+            0x00u8, 0x07, // transaction id
+            0x00, 0x00, // protocol id
+            0x00, 0x06, // length field (unit id + PDU)
+            0x11, // unit id
+            0x03, 0x00, 0x6B, 0x00, 0x03, // PDU
+        ];
+
+        // not yet complete
+        let mut output = [0u8;20];
+        for b in &frame[..frame.len() - 1] {
+            buff.push(*b);
+        }
+        let decoded = buff.try_decode_frame(&mut output);
+        assert_eq!(decoded, None);
+
+        buff.push(frame[frame.len() - 1]);
+
+        let decoded = buff.try_decode_frame(&mut output);
+        assert_eq!(decoded, Some((6, Some(0x0007))));
+        assert_eq!(&output[..6], &[0x11u8, 0x03, 0x00, 0x6B, 0x00, 0x03]);
+        assert_eq!(buff.len(), 0usize);
+    }
+
+    #[test]
+    fn test_tcp_mbap_rejects_nonzero_protocol_id() {
+        let mut buff = ModbusBuffer::<20>::new().protocol(Protocol::TcpMbap);
+
+        let frame = [0x00u8, 0x07, 0x00, 0x01, 0x00, 0x06, 0x11, 0x03, 0x00, 0x6B, 0x00, 0x03];
+        for b in frame {
+            buff.push(b);
+        }
+
+        let mut output = [0u8;20];
+        let decoded = buff.try_decode_frame(&mut output);
+        assert_eq!(decoded, None);
+    }
+
+    #[test]
+    #[should_panic]
+    fn test_enqueue_slice_overflow_should_panic() {
+        let mut buff = ModbusBuffer::<8>::new()
+            .overwrite(false);
+
+        // does not fit in 8 bytes of free space, should panic like `push` does
+        buff.enqueue_slice(&[0u8; 9]);
+    }
+
+    #[test]
+    fn test_enqueue_slice_and_dequeue_many() {
+        let mut buff = ModbusBuffer::<8>::new();
+
+        let written = buff.enqueue_slice(&[0x00, 0x01, 0x02, 0x03, 0x04, 0x05]);
+        assert_eq!(written, 6);
+        assert_eq!(buff.len(), 6);
+
+        // only 2 bytes of free space remain
+        let written = buff.enqueue_slice(&[0x06, 0x07, 0x08]);
+        assert_eq!(written, 2);
+        assert_eq!(buff.len(), 8);
+
+        let removed = buff.dequeue_many(3);
+        assert_eq!(removed, 3);
+        assert_eq!(buff.len(), 5);
+
+        let mut output = [0u8;8];
+        let len = buff.frame(&mut output);
+        assert_eq!(len, Some(5));
+        assert_eq!(&output[..5], &[0x03u8, 0x04, 0x05, 0x06, 0x07]);
+    }
+
+    #[test]
+    fn test_enqueue_slice_wraps_across_two_chunks() {
+        let mut buff = ModbusBuffer::<8>::new();
+
+        // move head/tail forward so the next enqueue straddles the end of the backing array
+        buff.enqueue_slice(&[0x00, 0x01, 0x02, 0x03, 0x04, 0x05]);
+        buff.dequeue_many(6);
+        assert_eq!(buff.len(), 0);
+
+        let written = buff.enqueue_slice(&[0xAA, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF]);
+        assert_eq!(written, 6);
+
+        let mut output = [0u8;8];
+        let len = buff.frame(&mut output);
+        assert_eq!(len, Some(6));
+        assert_eq!(&output[..6], &[0xAAu8, 0xBB, 0xCC, 0xDD, 0xEE, 0xFF]);
+    }
+
+    #[test]
+    fn test_noise_before_and_after_frame() {
+        let mut buff = ModbusBuffer::<20>::new();
+
+        // noise, then a valid frame, then more noise
+        for b in [0x01u8, 0x02, 0x03, 0x04] {
+            buff.push(b);
+        }
+        for b in [0x12u8, 0x06, 0x22, 0x22, 0xAB, 0xCD, 0x9F, 0xBE] {
+            buff.push(b);
+        }
+        for b in [0x05u8, 0x06] {
+            buff.push(b);
+        }
+
+        let mut output = [0u8;20];
+        let len = buff.try_decode_frame(&mut output);
+        assert_eq!(len, Some((6, None)));
+        assert_eq!(&output[..6], &[0x12u8, 0x06, 0x22, 0x22, 0xAB, 0xCD]);
+    }
+
+    #[test]
+    fn test_scan_order_agrees_with_multiple_candidate_frames() {
+        // a buffer holding two independently CRC-valid frames of different lengths must
+        // decode to the same one regardless of the `crc-table` feature: the table-driven
+        // scan has to visit candidates in the same forward/reverse-interleaved order as the
+        // non-table scan, not just find *a* valid frame somewhere.
+        let mut buff = ModbusBuffer::<32>::new();
+
+        // 15 zero payload bytes + CRC: a 17-byte frame
+        for b in [0u8; 15] {
+            buff.push(b);
+        }
+        buff.push(0x40);
+        buff.push(0xBF);
+
+        // 3 zero payload bytes + CRC: a 5-byte frame
+        for b in [0u8; 3] {
+            buff.push(b);
+        }
+        buff.push(0x71);
+        buff.push(0xC0);
+
+        let mut output = [0u8;32];
+        let len = buff.try_decode_frame(&mut output);
+        assert_eq!(len, Some((3, None)));
+        assert_eq!(&output[..3], &[0u8, 0, 0]);
+    }
+
+    #[test]
+    fn test_decode_status_partial_then_complete() {
+        let mut buff = ModbusBuffer::<10>::new();
+        let mut output = [0u8;10];
+
+        buff.push(0x12);
+        buff.push(0x06);
+        assert_eq!(buff.try_decode(&mut output), DecodeStatus::Partial);
+
+        for b in [0x22u8, 0x22, 0xAB, 0xCD, 0x9F, 0xBE] {
+            buff.push(b);
+        }
+
+        assert_eq!(
+            buff.try_decode(&mut output),
+            DecodeStatus::Complete { len: 6, transaction_id: None }
+        );
+    }
+
+    #[test]
+    fn test_decode_status_invalid_recovers_saturated_buffer() {
+        // buffer that can never contain a valid CRC-matching frame, filled to capacity
+        let mut buff = ModbusBuffer::<6>::new();
+        for b in [0xFFu8, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF] {
+            buff.push(b);
+        }
+        assert!(buff.is_full());
+
+        let mut output = [0u8;6];
+        assert_eq!(buff.try_decode(&mut output), DecodeStatus::Invalid { discarded: 1 });
+        // the buffer should have made room, rather than staying stuck forever
+        assert_eq!(buff.len(), 5);
+    }
+}
+
+/// Property-based model test cross-checking `ModbusBuffer` against a naive, `std`-only
+/// reference decoder over random sequences of operations. Gated behind the `model-test`
+/// feature since it pulls in `bolero` and `std`, neither appropriate for the default
+/// `no_std` build.
+#[cfg(all(test, feature = "model-test"))]
+mod model_test {
+    extern crate std;
+
+    use super::*;
+    use bolero::{check, generator::*};
+    use std::collections::VecDeque;
+    use std::vec::Vec;
+
+    const MODEL_CAPACITY: usize = 32;
+
+    #[derive(Debug, Clone, TypeGenerator)]
+    struct FrameBody {
+        address: u8,
+        function_code: u8,
+        #[generator(Vec::gen().with().len(0usize..=20))]
+        data: Vec<u8>,
+    }
+
+    impl FrameBody {
+        /// Encodes this body as a complete, CRC-valid RTU frame.
+        fn encode(&self) -> Vec<u8> {
+            let mut frame = Vec::with_capacity(2 + self.data.len() + 2);
+            frame.push(self.address);
+            frame.push(self.function_code);
+            frame.extend_from_slice(&self.data);
+            let crc = ModbusBuffer::<MODEL_CAPACITY>::crc16(&frame);
+            frame.push((crc >> 8) as u8);
+            frame.push(crc as u8);
+            frame
+        }
+    }
+
+    #[derive(Debug, Clone, TypeGenerator)]
+    enum Op {
+        Push(u8),
+        PushFrame(FrameBody),
+        TryDecode,
+    }
+
+    /// Reference implementation: a plain `VecDeque<u8>` plus a brute-force CRC scan, used as
+    /// an oracle `ModbusBuffer`'s behaviour is cross-checked against.
+    #[derive(Default)]
+    struct ReferenceBuffer {
+        data: VecDeque<u8>,
+    }
+
+    impl ReferenceBuffer {
+        fn push(&mut self, byte: u8) {
+            if self.data.len() == MODEL_CAPACITY {
+                self.data.pop_front();
+            }
+            self.data.push_back(byte);
+        }
+
+        fn frame(&self) -> Vec<u8> {
+            self.data.iter().copied().collect()
+        }
+
+        /// Naively scans for a valid CRC, mirroring `ModbusBuffer`'s actual scan order: for
+        /// each window size, ascending from the minimum, a forward probe at `i` followed by a
+        /// reverse-mirrored probe at `j` before growing the window, rather than fully growing
+        /// the window from one start index before moving to the next. Also mirrors
+        /// `give_up_if_full`: when the buffer is saturated and nothing decodes, the oldest
+        /// byte is discarded as noise instead of leaving the buffer stuck.
+        fn try_decode(&mut self) -> Option<Vec<u8>> {
+            const MIN_FRAME_LEN: usize = 3;
+            let buf = self.frame();
+            let mut window_size = MIN_FRAME_LEN + 2;
+
+            'scan: while window_size <= buf.len() {
+                for i in 0..=buf.len() - window_size {
+                    if ModbusBuffer::<MODEL_CAPACITY>::check_crc(&buf[i..i + window_size]) {
+                        let decoded = buf[i..i + window_size - 2].to_vec();
+                        for _ in 0..i + window_size {
+                            self.data.pop_front();
+                        }
+                        return Some(decoded);
+                    }
+
+                    if buf.len() == window_size {
+                        break 'scan;
+                    }
+
+                    let j = buf.len() - i - window_size;
+                    if ModbusBuffer::<MODEL_CAPACITY>::check_crc(&buf[j..j + window_size]) {
+                        let decoded = buf[j..j + window_size - 2].to_vec();
+                        for _ in 0..j + window_size {
+                            self.data.pop_front();
+                        }
+                        return Some(decoded);
+                    }
+                }
+                window_size += 1;
+            }
+
+            if self.data.len() == MODEL_CAPACITY {
+                self.data.pop_front();
+            }
+            None
+        }
+    }
+
+    #[test]
+    fn model_test() {
+        check!().with_type::<Vec<Op>>().for_each(|ops| {
+            let mut buff = ModbusBuffer::<MODEL_CAPACITY>::new();
+            let mut reference = ReferenceBuffer::default();
+            let mut output = [0u8; MODEL_CAPACITY];
+
+            for op in ops {
+                match op {
+                    Op::Push(byte) => {
+                        buff.push(*byte);
+                        reference.push(*byte);
+                    }
+                    Op::PushFrame(body) => {
+                        for byte in body.encode() {
+                            buff.push(byte);
+                            reference.push(byte);
+                        }
+                    }
+                    Op::TryDecode => {
+                        // invariant: len() never exceeds CAPACITY
+                        assert!(buff.len() <= MODEL_CAPACITY);
+
+                        // invariant: frame() output always equals the logical contents in order
+                        let mut snapshot = [0u8; MODEL_CAPACITY];
+                        let snapshot_len = buff.frame(&mut snapshot).unwrap();
+                        assert_eq!(&snapshot[..snapshot_len], reference.frame().as_slice());
+
+                        let decoded = buff.try_decode_frame(&mut output);
+                        let reference_decoded = reference.try_decode();
+
+                        match (decoded, reference_decoded) {
+                            (Some((len, _)), Some(reference_bytes)) => {
+                                assert_eq!(&output[..len], reference_bytes.as_slice());
+
+                                // invariant: every frame returned by try_decode_frame passes
+                                // check_crc, recomputed over the pre-decode snapshot
+                                let mut found = false;
+                                for i in 0..=snapshot_len.saturating_sub(len + 2) {
+                                    if snapshot[i..i + len] == output[..len]
+                                        && ModbusBuffer::<MODEL_CAPACITY>::check_crc(
+                                            &snapshot[i..i + len + 2],
+                                        )
+                                    {
+                                        found = true;
+                                        break;
+                                    }
+                                }
+                                assert!(found);
+                            }
+                            (None, None) => {}
+                            other => panic!(
+                                "ModbusBuffer and the reference decoder disagreed: {other:?}"
+                            ),
+                        }
+                    }
+                }
+            }
+        });
+    }
 }